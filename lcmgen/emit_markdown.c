@@ -0,0 +1,416 @@
+#include <stdio.h>
+#include <stdint.h>
+#include <string.h>
+#ifdef WIN32
+#define __STDC_FORMAT_MACROS
+#endif
+#include <inttypes.h>
+
+#include "lcmgen.h"
+
+#include "getopt.h"
+
+#ifdef WIN32
+#include <lcm/windows/WinPorting.h>
+#endif
+
+#define emit(...) do { fprintf(f, __VA_ARGS__); fprintf(f, "\n"); } while (0)
+#define emit_blank() fprintf(f, "\n")
+
+void setup_markdown_options(getopt_t *gopt)
+{
+    getopt_add_string(gopt, 0,   "mdpath",     "",         "Markdown documentation destination directory");
+}
+
+static char *make_page_path(lcmgen_t *lcm, const char *name)
+{
+    const char *mdpath = getopt_get_string(lcm->gopt, "mdpath");
+    return g_strdup_printf("%s%s%s.md",
+                           mdpath,
+                           strlen(mdpath) > 0 ? G_DIR_SEPARATOR_S : "",
+                           name);
+}
+
+static lcm_struct_t *find_struct(lcmgen_t *lcm, const char *lctypename)
+{
+    for (unsigned int i = 0; i < g_ptr_array_size(lcm->structs); i++) {
+        lcm_struct_t *ls = (lcm_struct_t *) g_ptr_array_index(lcm->structs, i);
+        if (!strcmp(ls->structname->lctypename, lctypename))
+            return ls;
+    }
+    return NULL;
+}
+
+static lcm_enum_t *find_enum(lcmgen_t *lcm, const char *lctypename)
+{
+    for (unsigned int i = 0; i < g_ptr_array_size(lcm->enums); i++) {
+        lcm_enum_t *le = (lcm_enum_t *) g_ptr_array_index(lcm->enums, i);
+        if (!strcmp(le->enumname->lctypename, lctypename))
+            return le;
+    }
+    return NULL;
+}
+
+// Computes the fingerprint that generated code reports on the wire, using the
+// same recursive scheme as the __<type>_hash_recursive() functions emitted by
+// the C generator. Returns 0 if a nested type was not among the parsed files,
+// in which case the fingerprint can't be determined.
+static int compute_fingerprint(lcmgen_t *lcm, lcm_struct_t *ls, GPtrArray *parents,
+                               uint64_t *result)
+{
+    for (unsigned int i = 0; i < g_ptr_array_size(parents); i++) {
+        if (g_ptr_array_index(parents, i) == ls) {
+            *result = 0;
+            return 1;
+        }
+    }
+
+    g_ptr_array_add(parents, ls);
+
+    uint64_t hash = (uint64_t) ls->hash;
+    int ok = 1;
+    for (unsigned int m = 0; ok && m < g_ptr_array_size(ls->members); m++) {
+        lcm_member_t *lm = (lcm_member_t *) g_ptr_array_index(ls->members, m);
+        if (lcm_is_primitive_type(lm->type->lctypename))
+            continue;
+
+        // Enums contribute their constant hash, as in the generated C code.
+        lcm_enum_t *le = find_enum(lcm, lm->type->lctypename);
+        if (le != NULL) {
+            hash += (uint64_t) le->hash;
+            continue;
+        }
+
+        lcm_struct_t *child = find_struct(lcm, lm->type->lctypename);
+        uint64_t child_hash = 0;
+        if (child == NULL || !compute_fingerprint(lcm, child, parents, &child_hash))
+            ok = 0;
+        hash += child_hash;
+    }
+
+    g_ptr_array_remove_index(parents, g_ptr_array_size(parents) - 1);
+
+    *result = (hash << 1) + ((hash >> 63) & 1);
+    return ok;
+}
+
+// Markdown table cells can't contain newlines or unescaped pipes. HTML
+// special characters are escaped too, so that "<type>" isn't taken as a tag.
+static char *table_cell(const char *s)
+{
+    if (s == NULL)
+        return g_strdup("");
+
+    char *trimmed = g_strstrip(g_strdup(s));
+    GString *out = g_string_new("");
+    for (const char *p = trimmed; *p; p++) {
+        if (*p == '\n')
+            g_string_append(out, "<br>");
+        else if (*p == '|')
+            g_string_append(out, "\\|");
+        else if (*p == '<')
+            g_string_append(out, "&lt;");
+        else if (*p == '>')
+            g_string_append(out, "&gt;");
+        else if (*p == '&')
+            g_string_append(out, "&amp;");
+        else
+            g_string_append_c(out, *p);
+    }
+    g_free(trimmed);
+    return g_string_free(out, FALSE);
+}
+
+static void emit_type_link(lcmgen_t *lcm, FILE *f, const char *lctypename)
+{
+    if (find_struct(lcm, lctypename) != NULL || find_enum(lcm, lctypename) != NULL)
+        fprintf(f, "[`%s`](%s.md)", lctypename, lctypename);
+    else
+        fprintf(f, "`%s`", lctypename);
+}
+
+static char *make_dimensions(lcm_member_t *lm)
+{
+    GString *out = g_string_new("");
+    for (unsigned int d = 0; d < g_ptr_array_size(lm->dimensions); d++) {
+        lcm_dimension_t *dim = (lcm_dimension_t *) g_ptr_array_index(lm->dimensions, d);
+        g_string_append_printf(out, "[%s]", dim->size);
+    }
+    return g_string_free(out, FALSE);
+}
+
+// Only the file name is shown, so that the pages don't depend on where the
+// sources were checked out.
+static void emit_location(FILE *f, lcm_typename_t *tn, const char *lcmfile)
+{
+    char *source = g_path_get_basename(lcmfile);
+    emit("| Package | `%s` |", strlen(tn->package) > 0 ? tn->package : "(none)");
+    emit("| Source | `%s` |", source);
+    g_free(source);
+}
+
+static void emit_used_by(lcmgen_t *lcm, FILE *f, const char *lctypename)
+{
+    int used_by = 0;
+    for (unsigned int i = 0; i < g_ptr_array_size(lcm->structs); i++) {
+        lcm_struct_t *other = (lcm_struct_t *) g_ptr_array_index(lcm->structs, i);
+        for (unsigned int m = 0; m < g_ptr_array_size(other->members); m++) {
+            lcm_member_t *lm = (lcm_member_t *) g_ptr_array_index(other->members, m);
+            if (strcmp(lm->type->lctypename, lctypename))
+                continue;
+
+            if (!used_by) {
+                emit("## Used by");
+                emit_blank();
+                used_by = 1;
+            }
+            fprintf(f, "- ");
+            emit_type_link(lcm, f, other->structname->lctypename);
+            emit_blank();
+            break;
+        }
+    }
+    if (used_by)
+        emit_blank();
+}
+
+static void emit_struct_page(lcmgen_t *lcm, FILE *f, lcm_struct_t *ls)
+{
+    emit("# %s", ls->structname->lctypename);
+    emit_blank();
+
+    if (ls->comment) {
+        emit("%s", ls->comment);
+        emit_blank();
+    }
+
+    emit("| | |");
+    emit("|---|---|");
+    emit_location(f, ls->structname, ls->lcmfile);
+    emit("| Base hash | `0x%016"PRIx64"` |", ls->hash);
+
+    GPtrArray *parents = g_ptr_array_new();
+    uint64_t fingerprint;
+    if (compute_fingerprint(lcm, ls, parents, &fingerprint))
+        emit("| Fingerprint | `0x%016"PRIx64"` |", fingerprint);
+    else
+        emit("| Fingerprint | unknown (depends on types that were not parsed) |");
+    g_ptr_array_free(parents, TRUE);
+    emit_blank();
+
+    if (g_ptr_array_size(ls->constants) > 0) {
+        emit("## Constants");
+        emit_blank();
+        emit("| Name | Type | Value | Description |");
+        emit("|---|---|---|---|");
+        for (unsigned int i = 0; i < g_ptr_array_size(ls->constants); i++) {
+            lcm_constant_t *lc = (lcm_constant_t *) g_ptr_array_index(ls->constants, i);
+            char *desc = table_cell(lc->comment);
+            emit("| `%s` | `%s` | `%s` | %s |", lc->membername, lc->lctypename, lc->val_str, desc);
+            g_free(desc);
+        }
+        emit_blank();
+    }
+
+    emit("## Members");
+    emit_blank();
+    if (g_ptr_array_size(ls->members) == 0) {
+        emit("This type has no members.");
+    } else {
+        emit("| Name | Type | Dimensions | Description |");
+        emit("|---|---|---|---|");
+        for (unsigned int i = 0; i < g_ptr_array_size(ls->members); i++) {
+            lcm_member_t *lm = (lcm_member_t *) g_ptr_array_index(ls->members, i);
+            char *dims = make_dimensions(lm);
+            char *desc = table_cell(lm->comment);
+
+            fprintf(f, "| `%s` | ", lm->membername);
+            emit_type_link(lcm, f, lm->type->lctypename);
+            if (strlen(dims) > 0)
+                emit(" | `%s` | %s |", dims, desc);
+            else
+                emit(" | | %s |", desc);
+
+            g_free(dims);
+            g_free(desc);
+        }
+    }
+    emit_blank();
+
+    // Nesting graph: the types this one contains, and the types that contain it.
+    GPtrArray *uses = g_ptr_array_new();
+    for (unsigned int i = 0; i < g_ptr_array_size(ls->members); i++) {
+        lcm_member_t *lm = (lcm_member_t *) g_ptr_array_index(ls->members, i);
+        if (lcm_is_primitive_type(lm->type->lctypename))
+            continue;
+
+        int seen = 0;
+        for (unsigned int j = 0; j < g_ptr_array_size(uses); j++)
+            if (!strcmp((char *) g_ptr_array_index(uses, j), lm->type->lctypename))
+                seen = 1;
+        if (!seen)
+            g_ptr_array_add(uses, lm->type->lctypename);
+    }
+
+    if (g_ptr_array_size(uses) > 0) {
+        emit("## Uses");
+        emit_blank();
+        for (unsigned int i = 0; i < g_ptr_array_size(uses); i++) {
+            fprintf(f, "- ");
+            emit_type_link(lcm, f, (char *) g_ptr_array_index(uses, i));
+            emit_blank();
+        }
+        emit_blank();
+    }
+    g_ptr_array_free(uses, TRUE);
+
+    emit_used_by(lcm, f, ls->structname->lctypename);
+}
+
+static void emit_enum_page(lcmgen_t *lcm, FILE *f, lcm_enum_t *le)
+{
+    emit("# %s", le->enumname->lctypename);
+    emit_blank();
+
+    // Enum hashes only depend on the name, so the fingerprint is the hash.
+    emit("| | |");
+    emit("|---|---|");
+    emit_location(f, le->enumname, le->lcmfile);
+    emit("| Kind | enum |");
+    emit("| Fingerprint | `0x%016"PRIx64"` |", le->hash);
+    emit_blank();
+
+    emit("## Values");
+    emit_blank();
+    emit("| Name | Value |");
+    emit("|---|---|");
+    for (unsigned int i = 0; i < g_ptr_array_size(le->values); i++) {
+        lcm_enum_value_t *lev = (lcm_enum_value_t *) g_ptr_array_index(le->values, i);
+        emit("| `%s` | `%d` |", lev->valuename, lev->value);
+    }
+    emit_blank();
+
+    emit_used_by(lcm, f, le->enumname->lctypename);
+}
+
+static FILE *open_page(lcmgen_t *lcm, const char *name)
+{
+    char *path = make_page_path(lcm, name);
+    FILE *f = fopen(path, "w");
+    if (f == NULL)
+        perror(path);
+    g_free(path);
+    return f;
+}
+
+static gint compare_typenames(gconstpointer a, gconstpointer b)
+{
+    const lcm_typename_t *ta = *(const lcm_typename_t **) a;
+    const lcm_typename_t *tb = *(const lcm_typename_t **) b;
+
+    // Sort by package first so that each package gets exactly one section.
+    int res = strcmp(ta->package, tb->package);
+    if (res)
+        return res;
+    return strcmp(ta->shortname, tb->shortname);
+}
+
+static int emit_index(lcmgen_t *lcm)
+{
+    FILE *f = open_page(lcm, "index");
+    if (f == NULL)
+        return -1;
+
+    GPtrArray *sorted = g_ptr_array_new();
+    for (unsigned int i = 0; i < g_ptr_array_size(lcm->structs); i++) {
+        lcm_struct_t *ls = (lcm_struct_t *) g_ptr_array_index(lcm->structs, i);
+        g_ptr_array_add(sorted, ls->structname);
+    }
+    for (unsigned int i = 0; i < g_ptr_array_size(lcm->enums); i++) {
+        lcm_enum_t *le = (lcm_enum_t *) g_ptr_array_index(lcm->enums, i);
+        g_ptr_array_add(sorted, le->enumname);
+    }
+    g_ptr_array_sort(sorted, compare_typenames);
+
+    emit("# LCM type catalog");
+    emit_blank();
+
+    const char *package = NULL;
+    for (unsigned int i = 0; i < g_ptr_array_size(sorted); i++) {
+        lcm_typename_t *tn = (lcm_typename_t *) g_ptr_array_index(sorted, i);
+
+        if (package == NULL || strcmp(package, tn->package)) {
+            if (package != NULL)
+                emit_blank();
+            package = tn->package;
+            emit("## %s", strlen(package) > 0 ? package : "(no package)");
+            emit_blank();
+            emit("| Type | Description |");
+            emit("|---|---|");
+        }
+
+        // Only the first line of a struct's comment is used as a summary.
+        char *summary = NULL;
+        lcm_struct_t *ls = find_struct(lcm, tn->lctypename);
+        if (ls == NULL) {
+            summary = g_strdup("enum");
+        } else if (ls->comment) {
+            gchar **lines = g_strsplit(ls->comment, "\n", 2);
+            summary = table_cell(lines[0]);
+            g_strfreev(lines);
+        } else {
+            summary = g_strdup("");
+        }
+
+        emit("| [`%s`](%s.md) | %s |", tn->lctypename, tn->lctypename, summary);
+        g_free(summary);
+    }
+    emit_blank();
+
+    g_ptr_array_free(sorted, TRUE);
+    fclose(f);
+    return 0;
+}
+
+int emit_markdown(lcmgen_t *lcm)
+{
+    // The catalog is written to index.md, which a type named "index" in no
+    // package would also use.
+    if (find_struct(lcm, "index") != NULL || find_enum(lcm, "index") != NULL) {
+        fprintf(stderr, "Type 'index' conflicts with the Markdown catalog index.md.\n");
+        return -1;
+    }
+
+    const char *mdpath = getopt_get_string(lcm->gopt, "mdpath");
+    if (strlen(mdpath) > 0 && g_mkdir_with_parents(mdpath, 0755)) {
+        perror(mdpath);
+        return -1;
+    }
+
+    // Each page also depends on other types (the "Used by" list, the
+    // recursive fingerprint, and which links resolve), so --lazy is not
+    // honored and every page and the index are always regenerated.
+    for (unsigned int i = 0; i < g_ptr_array_size(lcm->structs); i++) {
+        lcm_struct_t *ls = (lcm_struct_t *) g_ptr_array_index(lcm->structs, i);
+
+        FILE *f = open_page(lcm, ls->structname->lctypename);
+        if (f == NULL)
+            return -1;
+
+        emit_struct_page(lcm, f, ls);
+        fclose(f);
+    }
+
+    for (unsigned int i = 0; i < g_ptr_array_size(lcm->enums); i++) {
+        lcm_enum_t *le = (lcm_enum_t *) g_ptr_array_index(lcm->enums, i);
+
+        FILE *f = open_page(lcm, le->enumname->lctypename);
+        if (f == NULL)
+            return -1;
+
+        emit_enum_page(lcm, f, le);
+        fclose(f);
+    }
+
+    return emit_index(lcm);
+}
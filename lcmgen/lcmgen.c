@@ -694,8 +694,10 @@ lcm_struct_t *parse_struct(lcmgen_t *lcmgen, const char *lcmfile, tokenize_t *t)
 
     parse_require(t, "{");
 
-    while (!parse_try_consume(t, "}")) {
-        // Check for leading comments that will be used to document the member.
+    while (1) {
+        // Check for leading comments that will be used to document the
+        // member. This must happen before parse_try_consume(), which would
+        // otherwise discard them.
         parse_try_consume_comment(lcmgen, t, 1);
 
         if (parse_try_consume(t, "}")) {
@@ -39,6 +39,9 @@ int emit_csharp(lcmgen_t *lcm);
 void setup_cpp_options(getopt_t *gopt);
 int emit_cpp(lcmgen_t *lcm);
 
+void setup_markdown_options(getopt_t *gopt);
+int emit_markdown(lcmgen_t *lcm);
+
 int main(int argc, char *argv[])
 {
     getopt_t *gopt = getopt_create();
@@ -78,6 +81,10 @@ int main(int argc, char *argv[])
     getopt_add_bool  (gopt, 0, "csharp",      0,     "Emit C#.NET code");
     setup_csharp_options(gopt);
 
+    getopt_add_spacer(gopt, "**** Markdown options ****");
+    getopt_add_bool  (gopt, 0, "markdown",    0,     "Emit Markdown documentation");
+    setup_markdown_options(gopt);
+
     if (!getopt_parse(gopt, argc, argv, 1) || getopt_get_bool(gopt,"help")) {
         printf("Usage: %s [options] <input files>\n\n", argv[0]);
         getopt_do_usage(gopt);
@@ -156,6 +163,13 @@ int main(int argc, char *argv[])
         }
     }
 
+    if (getopt_get_bool(gopt, "markdown")) {
+        did_something = 1;
+        if (emit_markdown(lcm)) {
+            printf("An error occurred while emitting Markdown documentation.\n");
+        }
+    }
+
     if (did_something == 0) {
         printf("No actions specified. Try --help.\n");
     }
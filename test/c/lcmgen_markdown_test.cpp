@@ -0,0 +1,132 @@
+#include <stdlib.h>
+#include <string.h>
+#include <fstream>
+#include <sstream>
+#include <string>
+#include <gtest/gtest.h>
+
+#include "common.h"
+#include "lcmtest4_enum_member_t.h"
+#include "lcmtest4_enum_t.h"
+
+// Pages are generated at build time by lcm-gen --markdown (see
+// test/types/CMakeLists.txt).
+static std::string ReadMarkdown(const std::string& name) {
+    std::string path = std::string(LCMTEST_MARKDOWN_DIR) + "/" + name + ".md";
+    std::ifstream f(path.c_str());
+    EXPECT_TRUE(f.good()) << "Could not open " << path;
+    std::stringstream contents;
+    contents << f.rdbuf();
+    return contents.str();
+}
+
+// Returns the fingerprint reported on a type's page, or 0 if there is none.
+static uint64_t ReadFingerprint(const std::string& page) {
+    const std::string prefix = "| Fingerprint | `0x";
+    size_t pos = page.find(prefix);
+    if (pos == std::string::npos) {
+        return 0;
+    }
+    return strtoull(page.c_str() + pos + prefix.size(), NULL, 16);
+}
+
+static int CountOccurrences(const std::string& haystack, const std::string& needle) {
+    int count = 0;
+    for (size_t pos = haystack.find(needle); pos != std::string::npos;
+            pos = haystack.find(needle, pos + needle.size())) {
+        count++;
+    }
+    return count;
+}
+
+TEST(LCM_C, LcmgenMarkdownFingerprint) {
+    // The fingerprints rendered by lcm-gen must match the ones computed by
+    // the generated C code, including for nested and recursive types.
+    EXPECT_EQ((uint64_t)__lcmtest_primitives_t_get_hash(),
+            ReadFingerprint(ReadMarkdown("lcmtest.primitives_t")));
+    EXPECT_EQ((uint64_t)__lcmtest_primitives_list_t_get_hash(),
+            ReadFingerprint(ReadMarkdown("lcmtest.primitives_list_t")));
+    EXPECT_EQ((uint64_t)__lcmtest_node_t_get_hash(),
+            ReadFingerprint(ReadMarkdown("lcmtest.node_t")));
+    EXPECT_EQ((uint64_t)__lcmtest_multidim_array_t_get_hash(),
+            ReadFingerprint(ReadMarkdown("lcmtest.multidim_array_t")));
+    EXPECT_EQ((uint64_t)__lcmtest2_cross_package_t_get_hash(),
+            ReadFingerprint(ReadMarkdown("lcmtest2.cross_package_t")));
+    EXPECT_EQ((uint64_t)__lcmtest4_enum_t_get_hash(),
+            ReadFingerprint(ReadMarkdown("lcmtest4.enum_t")));
+    EXPECT_EQ((uint64_t)__lcmtest4_enum_member_t_get_hash(),
+            ReadFingerprint(ReadMarkdown("lcmtest4.enum_member_t")));
+}
+
+TEST(LCM_C, LcmgenMarkdownEnum) {
+    std::string page = ReadMarkdown("lcmtest4.enum_t");
+    EXPECT_NE(std::string::npos, page.find("| `ZERO` | `0` |"));
+    EXPECT_NE(std::string::npos, page.find("| `ONE` | `1` |"));
+    EXPECT_NE(std::string::npos, page.find("| `FORTY_TWO` | `42` |"));
+    EXPECT_NE(std::string::npos, page.find(
+            "- [`lcmtest4.enum_member_t`](lcmtest4.enum_member_t.md)"));
+
+    // Enum members link to the enum's page.
+    std::string member = ReadMarkdown("lcmtest4.enum_member_t");
+    EXPECT_NE(std::string::npos, member.find(
+            "| `values` | [`lcmtest4.enum_t`](lcmtest4.enum_t.md) | `[2]` |"));
+}
+
+TEST(LCM_C, LcmgenMarkdownConstants) {
+    std::string page = ReadMarkdown("lcmtest.exampleconst_t");
+    EXPECT_NE(std::string::npos, page.find("Struct with various constant definitions"));
+    EXPECT_NE(std::string::npos, page.find("| `DEF` | `int32_t` | `2` |"));
+    EXPECT_NE(std::string::npos, page.find("| `PI` | `double` | `3.1415926` |"));
+    EXPECT_NE(std::string::npos, page.find("| `LONG` | `int64_t` | `0xf0f0f0f0` |"));
+    // HTML special characters in comments are escaped.
+    EXPECT_NE(std::string::npos, page.find(
+            "| `ABC` | `int32_t` | `1` | Declare a constant field with syntax "
+            "\"const &lt;type&gt; &lt;name&gt; = &lt;value&gt;;\" |"));
+}
+
+TEST(LCM_C, LcmgenMarkdownComments) {
+    std::string page = ReadMarkdown("lcmtest.comments_t");
+    EXPECT_EQ(0u, page.find("# lcmtest.comments_t\n\nContains a number of primitive data types\n"));
+    EXPECT_NE(std::string::npos, page.find(
+            "| `field_a` | `int8_t` | | "
+            "Comments immediately preceding a field are attached to that field. |"));
+    // Newlines in comments become line breaks within the table cell.
+    EXPECT_NE(std::string::npos, page.find(
+            "| `field_b` | `int16_t` | | "
+            "Both single line comments.<br> And multi-line<br>comments. |"));
+    EXPECT_NE(std::string::npos, page.find("| `array` | `int32_t` | `[field_a]` |"));
+    // Only the file name of the source is shown.
+    EXPECT_NE(std::string::npos, page.find("| Source | `comments_t.lcm` |"));
+
+    // Pipes in comments are escaped.
+    std::string member = ReadMarkdown("lcmtest4.enum_member_t");
+    EXPECT_NE(std::string::npos, member.find("| Either ONE \\| FORTY_TWO |"));
+}
+
+TEST(LCM_C, LcmgenMarkdownNesting) {
+    std::string cross_package = ReadMarkdown("lcmtest2.cross_package_t");
+    EXPECT_NE(std::string::npos, cross_package.find(
+            "- [`lcmtest.primitives_t`](lcmtest.primitives_t.md)"));
+    EXPECT_NE(std::string::npos, cross_package.find(
+            "- [`lcmtest2.another_type_t`](lcmtest2.another_type_t.md)"));
+
+    std::string primitives = ReadMarkdown("lcmtest.primitives_t");
+    EXPECT_NE(std::string::npos, primitives.find(
+            "- [`lcmtest2.cross_package_t`](lcmtest2.cross_package_t.md)"));
+
+    // node_t contains itself.
+    std::string node = ReadMarkdown("lcmtest.node_t");
+    EXPECT_EQ(2, CountOccurrences(node, "- [`lcmtest.node_t`](lcmtest.node_t.md)"));
+}
+
+TEST(LCM_C, LcmgenMarkdownIndex) {
+    // Each package gets exactly one section in the index.
+    std::string index = ReadMarkdown("index");
+    EXPECT_EQ(1, CountOccurrences(index, "\n## lcmtest\n"));
+    EXPECT_EQ(1, CountOccurrences(index, "\n## lcmtest2\n"));
+    EXPECT_EQ(1, CountOccurrences(index, "\n## lcmtest3\n"));
+    EXPECT_EQ(1, CountOccurrences(index, "\n## lcmtest4\n"));
+    EXPECT_EQ(1, CountOccurrences(index, "| [`lcmtest4.enum_t`](lcmtest4.enum_t.md) | enum |"));
+    EXPECT_EQ(1, CountOccurrences(index, "[`lcmtest.exampleconst_t`](lcmtest.exampleconst_t.md)"));
+    EXPECT_EQ(1, CountOccurrences(index, "[`lcmtest.comments_t`](lcmtest.comments_t.md)"));
+}